@@ -1,14 +1,58 @@
 use std::collections::HashMap;
 use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
-use syn::{parse_macro_input, Data, DeriveInput, Fields};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields, LitStr};
 
 // TODO: wrap functions in a trait? would probably use the other (main) crate
 
-// TODO: own error enum (for update). also use the other (main) crate for this
 
+/// Column constraints declared on a field through `#[squail(...)]`.
+#[derive(Default)]
+struct FieldConstraints {
+    unique: bool,
+    not_null: bool,
+    index: bool,
+    blob: bool,
+    default: Option<String>,
+    references: Option<String>,
+}
+
+/// Parse the `#[squail(...)]` attribute (if present) on a field into its `FieldConstraints`.
+///
+/// Recognised keys: `unique`, `not_null`, `index`, `blob` (flags) and `default = "..."`,
+/// `references = "OtherTable(id)"` (string values).
+fn parse_field_constraints(field: &Field) -> FieldConstraints {
+    let mut constraints = FieldConstraints::default();
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("squail") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("unique") {
+                constraints.unique = true;
+            } else if meta.path.is_ident("not_null") {
+                constraints.not_null = true;
+            } else if meta.path.is_ident("index") {
+                constraints.index = true;
+            } else if meta.path.is_ident("blob") {
+                constraints.blob = true;
+            } else if meta.path.is_ident("default") {
+                constraints.default = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("references") {
+                constraints.references = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("unsupported #[squail(...)] field attribute"));
+            }
+            Ok(())
+        }).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    constraints
+}
 
-#[proc_macro_derive(Table)]
+#[proc_macro_derive(Table, attributes(squail))]
 pub fn derive_table(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -26,10 +70,22 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
     };
 
     let mut column_names = Vec::new();
+    let mut column_defs = Vec::new();
+    let mut index_sqls = Vec::new();
+    let mut migrate_column_defs = Vec::new();
+    let mut migrate_unique_index_sqls = Vec::new();
+    let mut migrate_index_sqls = Vec::new();
 
     let mut field_names = Vec::new();
     let mut field_getters = Vec::new();
     let mut field_accessors = Vec::new();
+    let mut row_field_accessors = Vec::new();
+
+    let mut non_id_field_names = Vec::new();
+    let mut non_id_field_types = Vec::new();
+
+    let mut blob_field_names = Vec::new();
+    let mut select_column_names = vec!["id".to_string()];
 
     let mut to_sql_trait_bounds = HashMap::new();
     let mut from_sql_trait_bounds = HashMap::new();
@@ -37,13 +93,23 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
     for field in fields.named.iter() {
         let field_name = field.ident.as_ref().unwrap();
         let field_type = &field.ty;
+        let constraints = parse_field_constraints(field);
 
         field_names.push(field_name);
-        field_getters.push(quote!(#field_name: row.get(stringify!(#field_name))?));
+        if constraints.blob {
+            // Blob fields are never read back through the generated query methods (use
+            // open_<field>_blob for that), so from_sql_row just fills in a placeholder.
+            field_getters.push(quote!(#field_name: Vec::new()));
+        } else {
+            field_getters.push(quote!(#field_name: row.get(stringify!(#field_name))?));
+        }
         field_accessors.push(quote!(self.#field_name));
+        row_field_accessors.push(quote!(row.#field_name));
 
         to_sql_trait_bounds.insert(stringify!(#field_type), quote!(#field_type: rusqlite::types::ToSql));
-        from_sql_trait_bounds.insert(stringify!(#field_type), quote!(#field_type: rusqlite::types::FromSql));
+        if !constraints.blob {
+            from_sql_trait_bounds.insert(stringify!(#field_type), quote!(#field_type: rusqlite::types::FromSql));
+        }
 
         if field_name == "id" {
             if let syn::Type::Path(type_path) = field_type {
@@ -58,12 +124,70 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
             }
         } else {
             column_names.push(field_name.to_string());
+            non_id_field_names.push(field_name);
+            non_id_field_types.push(field_type);
+
+            if !constraints.blob {
+                select_column_names.push(field_name.to_string());
+            }
+
+            let mut column_def = field_name.to_string();
+            let mut migrate_column_def = field_name.to_string();
+            if constraints.not_null {
+                column_def.push_str(" NOT NULL");
+                migrate_column_def.push_str(" NOT NULL");
+            }
+            if constraints.unique {
+                column_def.push_str(" UNIQUE");
+                // SQLite rejects `UNIQUE` on `ALTER TABLE ... ADD COLUMN`, so `migrate`
+                // adds the column bare and enforces uniqueness via a separate index.
+            }
+            if let Some(default) = &constraints.default {
+                column_def.push_str(&format!(" DEFAULT {}", default));
+                migrate_column_def.push_str(&format!(" DEFAULT {}", default));
+            }
+            if let Some(references) = &constraints.references {
+                column_def.push_str(&format!(" REFERENCES {}", references));
+                migrate_column_def.push_str(&format!(" REFERENCES {}", references));
+            }
+            column_defs.push(column_def);
+            migrate_column_defs.push(migrate_column_def);
+
+            migrate_unique_index_sqls.push(if constraints.unique {
+                format!(
+                    "CREATE UNIQUE INDEX IF NOT EXISTS uq_{}_{} ON {} ({});",
+                    table_name, field_name, table_name, field_name
+                )
+            } else {
+                String::new()
+            });
+
+            let index_sql = if constraints.index {
+                format!(
+                    "CREATE INDEX IF NOT EXISTS idx_{}_{} ON {} ({});",
+                    table_name, field_name, table_name, field_name
+                )
+            } else {
+                String::new()
+            };
+            if constraints.index {
+                index_sqls.push(index_sql.clone());
+            }
+            migrate_index_sqls.push(index_sql);
+
+            if constraints.blob {
+                blob_field_names.push(field_name);
+            }
         }
     }
 
     let to_sql_trait_bounds = to_sql_trait_bounds.values().collect::<Vec<_>>();
     let from_sql_trait_bounds = from_sql_trait_bounds.values().collect::<Vec<_>>();
 
+    // `#[squail(blob)]` fields are excluded here, so they're never loaded into memory by
+    // the generated query methods -- see `blob_fns` below.
+    let select_columns_sql = select_column_names.join(", ");
+
     if !field_names.iter().map(|id| id.to_string()).any(|id| &id == "id") {
         panic!("Structs annotated with `Table` require a primary key field `id: Option<i64>`.");
     }
@@ -72,21 +196,72 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
     let create_table_sql = format!(
         "CREATE TABLE IF NOT EXISTS {} (id INTEGER PRIMARY KEY AUTOINCREMENT, {});",
         table_name,
-        column_names.join(", ")
+        column_defs.join(", ")
     );
 
     let create_table_fn = quote! {
-        /// Use a `Connection` to create a table named after the struct (`#struct_name`)
+        /// Use a `Connection` to create a table named after the struct (`#struct_name`).
         /// If the table already exists, this returns `Ok(())` and does nothing.
-        pub fn create_table(conn: &rusqlite::Connection) -> rusqlite::Result<()>
+        ///
+        /// Also creates an index for every field annotated with `#[squail(index)]`
+        /// or `#[squail(unique)]`.
+        pub fn create_table(conn: &rusqlite::Connection) -> squail::Result<()>
             where #(#to_sql_trait_bounds),*
         {
-            conn.execute(#create_table_sql, [])?;
+            let wrap = squail::Error::sqlite(#table_name, "create_table");
+
+            conn.prepare_cached(#create_table_sql).map_err(wrap)?.execute([]).map_err(wrap)?;
+            #(conn.prepare_cached(#index_sqls).map_err(wrap)?.execute([]).map_err(wrap)?;)*
             Ok(())
         }
     };
 
 
+    let migrate_fn = quote! {
+        /// Bring an existing table's columns in line with the struct.
+        ///
+        /// Reads the table's current columns via `PRAGMA table_info`, diffs them against
+        /// this struct's compile-time column list, and runs `ALTER TABLE ... ADD COLUMN ...`
+        /// for each column the struct has that the table doesn't (reusing the same
+        /// `#[squail(...)]` constraint info as `create_table`). `#[squail(unique)]` columns
+        /// are added bare (SQLite rejects `UNIQUE` on `ADD COLUMN`) and then enforced via a
+        /// separate `CREATE UNIQUE INDEX`, and `#[squail(index)]` columns get the same
+        /// `CREATE INDEX IF NOT EXISTS` that `create_table` would have created for them on a
+        /// fresh database. Columns the table has but the struct doesn't are left untouched
+        /// (SQLite can't easily drop columns on old versions) and are instead returned for
+        /// the caller to inspect.
+        pub fn migrate(conn: &rusqlite::Connection) -> squail::Result<Vec<String>> {
+            let wrap = squail::Error::sqlite(#table_name, "migrate");
+
+            let struct_columns: &[(&str, &str, &str, &str)] =
+                &[#((#column_names, #migrate_column_defs, #migrate_unique_index_sqls, #migrate_index_sqls)),*];
+
+            let mut stmt = conn.prepare_cached(&format!("PRAGMA table_info({})", #table_name)).map_err(wrap)?;
+            let existing_columns = stmt.query_map([], |row| row.get::<_, String>(1)).map_err(wrap)?
+                .collect::<rusqlite::Result<std::collections::HashSet<String>>>().map_err(wrap)?;
+
+            for (name, column_def, unique_index_sql, index_sql) in struct_columns {
+                if !existing_columns.contains(*name) {
+                    conn.prepare_cached(&format!("ALTER TABLE {} ADD COLUMN {}", #table_name, column_def)).map_err(wrap)?.execute([]).map_err(wrap)?;
+                    if !unique_index_sql.is_empty() {
+                        conn.prepare_cached(unique_index_sql).map_err(wrap)?.execute([]).map_err(wrap)?;
+                    }
+                    if !index_sql.is_empty() {
+                        conn.prepare_cached(index_sql).map_err(wrap)?.execute([]).map_err(wrap)?;
+                    }
+                }
+            }
+
+            let struct_column_names: std::collections::HashSet<&str> =
+                struct_columns.iter().map(|(name, _, _, _)| *name).collect();
+
+            Ok(existing_columns.into_iter()
+                .filter(|column| column != "id" && !struct_column_names.contains(column.as_str()))
+                .collect())
+        }
+    };
+
+
     let insert_sql = format!(
         "INSERT INTO {} (id, {}) VALUES ({});",
         table_name,
@@ -97,10 +272,12 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
     let insert_fn = quote! {
         /// Insert struct instance into the table, setting `self.id` to
         /// `Some(last_insert_rowid())` if it was `None`.
-        pub fn insert(&mut self, conn: &rusqlite::Connection) -> rusqlite::Result<i64>
+        pub fn insert(&mut self, conn: &rusqlite::Connection) -> squail::Result<i64>
             where #(#to_sql_trait_bounds),*
         {
-            conn.execute(#insert_sql, rusqlite::params![#(#field_accessors),*])?;
+            let wrap = squail::Error::sqlite(#table_name, "insert");
+
+            conn.prepare_cached(#insert_sql).map_err(wrap)?.execute(rusqlite::params![#(#field_accessors),*]).map_err(wrap)?;
             // TODO: test this with manually set id. also test that this can't update!!!
             let id = conn.last_insert_rowid();
             self.id = Some(id);
@@ -112,7 +289,7 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
     let update_or_insert_fn = quote! {
         /// Update a table row using the calling struct instance.
         /// If the row does not yet exist, it is inserted into the table.
-        pub fn update_or_insert(&mut self, conn: &rusqlite::Connection) -> rusqlite::Result<i64>
+        pub fn update_or_insert(&mut self, conn: &rusqlite::Connection) -> squail::Result<i64>
             where #(#to_sql_trait_bounds),*
         {
             match self.id {
@@ -138,25 +315,84 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
     let update_fn = quote! {
         /// Update a table row using the calling struct instance.
         ///
-        /// If `id` is `None`, this fails with `InvalidQuery`.
-        /// If the row does not exist, this fails with `QueryReturnedNoRows`.
+        /// If `id` is `None`, this fails with `Error::MissingId`.
+        /// If the row does not exist, this fails with `Error::NotFound`.
         ///
         /// A version that inserts a new row instead also exists. See `update_or_insert`.
-        pub fn update(&self, conn: &rusqlite::Connection) -> rusqlite::Result<()>
+        pub fn update(&self, conn: &rusqlite::Connection) -> squail::Result<()>
             where #(#to_sql_trait_bounds),*
         {
             if self.id.is_none() {
-                return Err(rusqlite::Error::InvalidQuery)
+                return Err(squail::Error::MissingId { table: #table_name });
             }
-            let updated_count = conn.execute(#update_sql, rusqlite::params![#(#field_accessors),*])?;
+            let updated_count = conn.prepare_cached(#update_sql)
+                .and_then(|mut stmt| stmt.execute(rusqlite::params![#(#field_accessors),*]))
+                .map_err(squail::Error::sqlite(#table_name, "update"))?;
             match updated_count {
-                0 => Err(rusqlite::Error::QueryReturnedNoRows),
+                0 => Err(squail::Error::NotFound { table: #table_name, operation: "update" }),
                 _ => Ok(()),
             }
         }
     };
 
 
+    let insert_many_fn = quote! {
+        /// Insert every row of `rows` in a single transaction, reusing one cached
+        /// prepared statement, and assign `last_insert_rowid()` back into each row's `id`.
+        ///
+        /// This is dramatically faster than calling `insert` in a loop, which commits
+        /// (and `fsync`s) once per row.
+        pub fn insert_many(conn: &mut rusqlite::Connection, rows: &mut [Self]) -> squail::Result<()>
+            where #(#to_sql_trait_bounds),*
+        {
+            let wrap = squail::Error::sqlite(#table_name, "insert_many");
+
+            let tx = conn.transaction().map_err(wrap)?;
+            {
+                let mut stmt = tx.prepare_cached(#insert_sql).map_err(wrap)?;
+                for row in rows.iter_mut() {
+                    stmt.execute(rusqlite::params![#(#row_field_accessors),*]).map_err(wrap)?;
+                    row.id = Some(tx.last_insert_rowid());
+                }
+            }
+            tx.commit().map_err(wrap)
+        }
+    };
+
+
+    let upsert_many_fn = quote! {
+        /// Update-or-insert every row of `rows` in a single transaction, reusing cached
+        /// prepared statements for both the update and insert case.
+        ///
+        /// Behaves like calling `update_or_insert` on each row in a loop, but without
+        /// the per-row transaction and statement-parsing overhead.
+        pub fn upsert_many(conn: &mut rusqlite::Connection, rows: &mut [Self]) -> squail::Result<()>
+            where #(#to_sql_trait_bounds),*
+        {
+            let wrap = squail::Error::sqlite(#table_name, "upsert_many");
+
+            let tx = conn.transaction().map_err(wrap)?;
+            {
+                let mut update_stmt = tx.prepare_cached(#update_sql).map_err(wrap)?;
+                let mut insert_stmt = tx.prepare_cached(#insert_sql).map_err(wrap)?;
+
+                for row in rows.iter_mut() {
+                    let needs_insert = match row.id {
+                        None => true,
+                        Some(_) => update_stmt.execute(rusqlite::params![#(#row_field_accessors),*]).map_err(wrap)? == 0,
+                    };
+
+                    if needs_insert {
+                        insert_stmt.execute(rusqlite::params![#(#row_field_accessors),*]).map_err(wrap)?;
+                        row.id = Some(tx.last_insert_rowid());
+                    }
+                }
+            }
+            tx.commit().map_err(wrap)
+        }
+    };
+
+
     let sync_fn = quote! {
         /// Sync a struct instance with the database state.
         /// This "updates" the structs fields using its database entry.
@@ -164,16 +400,16 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
         /// Result contains `false` if `self.id == None` or if no row with that `id` was found.
         ///
         /// To update database entry using the structs fields, see `update`.
-        pub fn sync(&mut self, conn: &rusqlite::Connection) -> rusqlite::Result<bool>
+        pub fn sync(&mut self, conn: &rusqlite::Connection) -> squail::Result<bool>
             where #(#from_sql_trait_bounds),*
         {
             if self.id.is_none() {
                 return Ok(false);
             }
             match #struct_name::get_by_id(conn, self.id.unwrap()) {
-                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
-                Ok(person) => {
-                    *self = person;
+                Err(squail::Error::NotFound { .. }) => Ok(false),
+                Ok(row) => {
+                    *self = row;
                     Ok(true)
                 },
                 Err(e) => Err(e),
@@ -196,36 +432,130 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
 
     let get_by_id_fn = quote! {
         /// Get a person from the table using their `id` (corresponding to the sqlite rowid)
-        pub fn get_by_id(conn: &rusqlite::Connection, id: i64) -> rusqlite::Result<Self>
+        pub fn get_by_id(conn: &rusqlite::Connection, id: i64) -> squail::Result<Self>
         where
             Self: Sized,
             #(#from_sql_trait_bounds),*
         {
-            let mut stmt = conn.prepare(&format!("SELECT * FROM {} WHERE id = ?", #table_name))?;
-            let mut rows = stmt.query(rusqlite::params![id])?;
+            let wrap = squail::Error::sqlite(#table_name, "get_by_id");
 
-            if let Some(row) = rows.next()? {
-                Self::from_sql_row(row)
-            } else {
-                Err(rusqlite::Error::QueryReturnedNoRows)
+            let mut stmt = conn.prepare_cached(&format!("SELECT {} FROM {} WHERE id = ?", #select_columns_sql, #table_name)).map_err(wrap)?;
+            let mut rows = stmt.query(rusqlite::params![id]).map_err(wrap)?;
+
+            match rows.next().map_err(wrap)? {
+                Some(row) => Self::from_sql_row(row).map_err(wrap),
+                None => Err(squail::Error::NotFound { table: #table_name, operation: "get_by_id" }),
             }
         }
     };
 
 
+    let get_all_fn = quote! {
+        /// Fetch every row of the table.
+        pub fn get_all(conn: &rusqlite::Connection) -> squail::Result<Vec<Self>>
+        where
+            Self: Sized,
+            #(#from_sql_trait_bounds),*
+        {
+            let wrap = squail::Error::sqlite(#table_name, "get_all");
+
+            let mut stmt = conn.prepare_cached(&format!("SELECT {} FROM {}", #select_columns_sql, #table_name)).map_err(wrap)?;
+            // Bind the result before returning it: `stmt` would otherwise be dropped
+            // while still borrowed by the `MappedRows` this tail expression produces.
+            let rows = stmt.query_map([], Self::from_sql_row).map_err(wrap)?
+                .collect::<rusqlite::Result<Vec<Self>>>()
+                .map_err(wrap);
+            rows
+        }
+    };
+
+
+    let find_by_fns = non_id_field_names.iter().zip(non_id_field_types.iter()).map(|(field_name, field_type)| {
+        let fn_name = format_ident!("find_by_{}", field_name);
+        let find_by_sql = format!("SELECT {} FROM {} WHERE {} = ?", select_columns_sql, table_name, field_name);
+        let operation = format!("find_by_{}", field_name);
+
+        quote! {
+            /// Fetch every row whose `#field_name` column equals `value`.
+            pub fn #fn_name(conn: &rusqlite::Connection, value: &#field_type) -> squail::Result<Vec<Self>>
+            where
+                Self: Sized,
+                #(#from_sql_trait_bounds),*
+            {
+                let wrap = squail::Error::sqlite(#table_name, #operation);
+
+                let mut stmt = conn.prepare_cached(#find_by_sql).map_err(wrap)?;
+                // Bind the result before returning it: `stmt` would otherwise be dropped
+                // while still borrowed by the `MappedRows` this tail expression produces.
+                let rows = stmt.query_map(rusqlite::params![value], Self::from_sql_row).map_err(wrap)?
+                    .collect::<rusqlite::Result<Vec<Self>>>()
+                    .map_err(wrap);
+                rows
+            }
+        }
+    }).collect::<Vec<_>>();
+
+
+    let blob_fns = blob_field_names.iter().map(|field_name| {
+        let fn_name = format_ident!("open_{}_blob", field_name);
+        let column_name = field_name.to_string();
+        let operation = format!("open_{}_blob", field_name);
+
+        quote! {
+            /// Open the `#field_name` column of the row with the given `id` for
+            /// incremental BLOB I/O via SQLite's `sqlite3_blob_*` API, instead of
+            /// reading it into memory through `get_by_id`/`sync`.
+            ///
+            /// Pass `read_write: true` to also write through the returned
+            /// [`rusqlite::blob::Blob`], which implements `Read`/`Write`/`Seek`.
+            pub fn #fn_name(conn: &rusqlite::Connection, id: i64, read_write: bool) -> squail::Result<rusqlite::blob::Blob<'_>> {
+                conn.blob_open(rusqlite::DatabaseName::Main, #table_name, #column_name, id, !read_write)
+                    .map_err(squail::Error::sqlite(#table_name, #operation))
+            }
+        }
+    }).collect::<Vec<_>>();
+
+
+    let query_where_fn = quote! {
+        /// Fetch every row matching a caller-supplied `WHERE` clause.
+        ///
+        /// `where_clause` is inserted verbatim after `WHERE` (e.g. `"age > ?1"`), and
+        /// `params` are bound to its placeholders in order. This is an escape hatch for
+        /// queries the generated `find_by_*` methods don't cover; callers are responsible
+        /// for the clause being valid SQL for this table.
+        pub fn query_where(conn: &rusqlite::Connection, where_clause: &str, params: &[&dyn rusqlite::types::ToSql]) -> squail::Result<Vec<Self>>
+        where
+            Self: Sized,
+            #(#from_sql_trait_bounds),*
+        {
+            let wrap = squail::Error::sqlite(#table_name, "query_where");
+
+            let mut stmt = conn.prepare_cached(&format!("SELECT {} FROM {} WHERE {}", #select_columns_sql, #table_name, where_clause)).map_err(wrap)?;
+            // Bind the result before returning it: `stmt` would otherwise be dropped
+            // while still borrowed by the `MappedRows` this tail expression produces.
+            let rows = stmt.query_map(params, Self::from_sql_row).map_err(wrap)?
+                .collect::<rusqlite::Result<Vec<Self>>>()
+                .map_err(wrap);
+            rows
+        }
+    };
+
+
     let delete_fn = quote! {
         /// Delete row corresponding to the struct instance from the database.
         /// Deletes the entry with rowid equal to `self.id` without further checks.
         ///
         /// Result contains `true` if a row was deleted.
-        pub fn delete(&mut self, conn: &rusqlite::Connection) -> rusqlite::Result<bool> {
+        pub fn delete(&mut self, conn: &rusqlite::Connection) -> squail::Result<bool> {
             if self.id.is_none() {
                 return Ok(false);
             }
-            let updated_count = conn.execute(&format!(
+            let updated_count = conn.prepare_cached(&format!(
                     "DELETE FROM {} WHERE id = ?",
                     #table_name
-            ), rusqlite::params![self.id])?;
+            ))
+            .and_then(|mut stmt| stmt.execute(rusqlite::params![self.id]))
+            .map_err(squail::Error::sqlite(#table_name, "delete"))?;
             self.id = None;
             Ok(updated_count > 0)
         }
@@ -235,11 +565,13 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
         /// Delete a row from the database by rowid.
         ///
         /// Result contains `true` if a row was deleted.
-        pub fn delete_by_id(conn: &rusqlite::Connection, id: i64) -> rusqlite::Result<()> {
-            conn.execute(&format!(
+        pub fn delete_by_id(conn: &rusqlite::Connection, id: i64) -> squail::Result<()> {
+            conn.prepare_cached(&format!(
                     "DELETE FROM {} WHERE id = ?",
                     #table_name
-            ), rusqlite::params![id])?;
+            ))
+            .and_then(|mut stmt| stmt.execute(rusqlite::params![id]))
+            .map_err(squail::Error::sqlite(#table_name, "delete_by_id"))?;
             Ok(())
         }
     };
@@ -247,8 +579,10 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
 
     let drop_table_fn = quote! {
         /// Use a `Connection` to drop the table named after the struct (`#struct_name`)
-        pub fn drop_table(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
-            conn.execute(&format!("DROP TABLE {}", #table_name), [])?;
+        pub fn drop_table(conn: &rusqlite::Connection) -> squail::Result<()> {
+            conn.prepare_cached(&format!("DROP TABLE {}", #table_name))
+                .and_then(|mut stmt| stmt.execute([]))
+                .map_err(squail::Error::sqlite(#table_name, "drop_table"))?;
             Ok(())
         }
     };
@@ -257,12 +591,19 @@ pub fn derive_table(input: TokenStream) -> TokenStream {
     let expanded = quote! {
         impl #struct_name {
             #create_table_fn
+            #migrate_fn
             #insert_fn
+            #insert_many_fn
             #update_or_insert_fn
+            #upsert_many_fn
             #update_fn
             #sync_fn
             #from_sql_row_fn
             #get_by_id_fn
+            #get_all_fn
+            #(#find_by_fns)*
+            #query_where_fn
+            #(#blob_fns)*
             #delete_fn
             #delete_by_id_fn
             #drop_table_fn
@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// Error returned by the CRUD methods `#[derive(Table)]` generates.
+///
+/// Wraps the underlying [`rusqlite::Error`] with the table and operation that failed,
+/// and gives dedicated variants to conditions the generated methods used to signal by
+/// abusing specific `rusqlite::Error` variants (e.g. `update` returning `InvalidQuery`
+/// for a missing `id`).
+#[derive(Debug)]
+pub enum Error {
+    /// `update` was called on an instance whose `id` is `None`. (`update_or_insert` and
+    /// `upsert_many` insert such a row instead.)
+    MissingId { table: &'static str },
+    /// The operation expected to find or affect a row but no row matched.
+    NotFound { table: &'static str, operation: &'static str },
+    /// Any other error returned by the underlying SQLite driver.
+    Sqlite { table: &'static str, operation: &'static str, source: rusqlite::Error },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingId { table } => write!(f, "{table}: `id` must be `Some` for this operation"),
+            Error::NotFound { table, operation } => write!(f, "{table}: {operation} matched no row"),
+            Error::Sqlite { table, operation, source } => write!(f, "{table}: {operation} failed: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Sqlite { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// Build a `rusqlite::Error -> Error` wrapper for a given table/operation.
+    ///
+    /// `#[derive(Table)]` generates one call to this per method (`let wrap =
+    /// squail::Error::sqlite(table, operation);`) instead of repeating the
+    /// `Error::Sqlite { table, operation, source }` closure literal at every call site.
+    /// There's no plain `From<rusqlite::Error>` because `Error::Sqlite` always needs this
+    /// table/operation context to be useful to callers.
+    pub fn sqlite(table: &'static str, operation: &'static str) -> impl Fn(rusqlite::Error) -> Error + Copy {
+        move |source| Error::Sqlite { table, operation, source }
+    }
+}
+
+/// Convenience alias for `Result<T, squail::Error>`.
+pub type Result<T> = std::result::Result<T, Error>;
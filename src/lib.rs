@@ -0,0 +1,13 @@
+//! squail: derive SQLite CRUD methods for plain structs.
+
+// The generated `#[derive(Table)]` code (and our own tests, which live inside this
+// crate) refers to the crate by its published name, `squail::...`. Without this, that
+// path doesn't resolve from inside the crate itself.
+extern crate self as squail;
+
+mod error;
+#[cfg(test)]
+mod test;
+
+pub use error::{Error, Result};
+pub use squail_macros::Table;